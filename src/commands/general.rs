@@ -0,0 +1,134 @@
+use serenity::client::bridge::gateway::ShardId;
+use serenity::client::Context;
+use serenity::framework::standard::macros::{command, group, owners_only};
+use serenity::framework::standard::{Args, CommandResult};
+use serenity::model::channel::Message;
+
+use crate::db::{self, DatabaseContainer};
+use crate::{ShardManagerContainer, SlimeError};
+
+#[group]
+#[commands(ping, latency, quit, prefix)]
+pub struct General;
+
+#[command]
+async fn ping(ctx: &Context, msg: &Message) -> CommandResult {
+    msg.channel_id
+        .say(&ctx.http, "Pong!")
+        .await
+        .map_err(SlimeError::Serenity)?;
+
+    Ok(())
+}
+
+#[command]
+async fn latency(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+
+    let shard_manager = match data.get::<ShardManagerContainer>() {
+        Some(shard_manager) => shard_manager,
+        None => {
+            msg.reply(ctx, "I don't have access to the shard manager right now.")
+                .await
+                .map_err(SlimeError::Serenity)?;
+            return Ok(());
+        }
+    };
+
+    let manager = shard_manager.lock().await;
+    let runners = manager.runners.lock().await;
+
+    let runner = match runners.get(&ShardId(ctx.shard_id)) {
+        Some(runner) => runner,
+        None => {
+            msg.reply(ctx, "No active shard found for this connection.")
+                .await
+                .map_err(SlimeError::Serenity)?;
+            return Ok(());
+        }
+    };
+
+    let latency_msg = match runner.latency {
+        Some(latency) => format!("The shard latency is {latency:?}."),
+        None => "I haven't received a heartbeat acknowledgement yet, so I can't report a latency.".to_string(),
+    };
+
+    msg.reply(ctx, latency_msg).await.map_err(SlimeError::Serenity)?;
+
+    Ok(())
+}
+
+// Shuts down every shard, so it's restricted to the bot's owner(s) - anyone
+// else who can message the bot would otherwise be able to kill the process.
+#[command]
+#[owners_only]
+async fn quit(ctx: &Context, msg: &Message) -> CommandResult {
+    let data = ctx.data.read().await;
+
+    let shard_manager = match data.get::<ShardManagerContainer>() {
+        Some(shard_manager) => shard_manager,
+        None => {
+            msg.reply(ctx, "I don't have access to the shard manager, so I can't shut down cleanly.")
+                .await
+                .map_err(SlimeError::Serenity)?;
+            return Ok(());
+        }
+    };
+
+    msg.reply(ctx, "Shutting down, see you later!")
+        .await
+        .map_err(SlimeError::Serenity)?;
+
+    shard_manager.lock().await.shutdown_all().await;
+
+    Ok(())
+}
+
+/// Shows or sets this server's custom command prefix, persisted in
+/// `guild_prefixes` so it survives a restart.
+#[command]
+async fn prefix(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let Some(guild_id) = msg.guild_id else {
+        msg.reply(ctx, "This command only works in a server.")
+            .await
+            .map_err(SlimeError::Serenity)?;
+        return Ok(());
+    };
+
+    let data = ctx.data.read().await;
+
+    let pool = match data.get::<DatabaseContainer>() {
+        Some(pool) => pool,
+        None => {
+            msg.reply(ctx, "I don't have access to the database right now.")
+                .await
+                .map_err(SlimeError::Serenity)?;
+            return Ok(());
+        }
+    };
+
+    if args.is_empty() {
+        let current = db::guild_prefix(pool, guild_id.0 as i64)
+            .await
+            .map_err(SlimeError::Database)?;
+
+        let reply = match current {
+            Some(prefix) => format!("This server's prefix is `{prefix}`."),
+            None => "This server hasn't set a custom prefix.".to_string(),
+        };
+
+        msg.reply(ctx, reply).await.map_err(SlimeError::Serenity)?;
+        return Ok(());
+    }
+
+    let new_prefix = args.single::<String>()?;
+    db::set_guild_prefix(pool, guild_id.0 as i64, &new_prefix)
+        .await
+        .map_err(SlimeError::Database)?;
+
+    msg.reply(ctx, format!("Prefix updated to `{new_prefix}`."))
+        .await
+        .map_err(SlimeError::Serenity)?;
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+use serenity::prelude::GatewayIntents;
+
+const DEFAULT_CONFIG_PATH: &str = "slime.toml";
+
+/// Settings that used to be scattered across `main` as hardcoded constants
+/// and a single env var lookup. Loaded from `slime.toml`, with
+/// `DISCORD_TOKEN` (if set) overriding whatever token the file contains, so
+/// an operator can keep the token out of the file entirely.
+#[derive(Deserialize)]
+pub struct Config {
+    pub token: Option<String>,
+    #[serde(default = "default_prefix")]
+    pub prefix: String,
+    #[serde(default = "default_intents")]
+    pub intents: Vec<String>,
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    #[serde(default = "default_log_dir")]
+    pub log_dir: String,
+    #[serde(default = "default_log_file_prefix")]
+    pub log_file_prefix: String,
+    #[serde(default = "default_database_url")]
+    pub database_url: String,
+    pub invite_url: Option<String>,
+}
+
+// Hand-written so the token never ends up in a log line via `{config:?}` -
+// see the chunk0-4 fix for why that's worth guarding against.
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("token", &self.token.as_ref().map(|_| "***"))
+            .field("prefix", &self.prefix)
+            .field("intents", &self.intents)
+            .field("log_level", &self.log_level)
+            .field("log_dir", &self.log_dir)
+            .field("log_file_prefix", &self.log_file_prefix)
+            .field("database_url", &self.database_url)
+            .field("invite_url", &self.invite_url)
+            .finish()
+    }
+}
+
+fn default_prefix() -> String {
+    "!".to_string()
+}
+
+fn default_intents() -> Vec<String> {
+    vec![
+        "GUILD_MESSAGES".to_string(),
+        "DIRECT_MESSAGES".to_string(),
+        "MESSAGE_CONTENT".to_string(),
+    ]
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_log_dir() -> String {
+    "logs".to_string()
+}
+
+fn default_log_file_prefix() -> String {
+    "slime".to_string()
+}
+
+fn default_database_url() -> String {
+    "sqlite://slime.db?mode=rwc".to_string()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            token: None,
+            prefix: default_prefix(),
+            intents: default_intents(),
+            log_level: default_log_level(),
+            log_dir: default_log_dir(),
+            log_file_prefix: default_log_file_prefix(),
+            database_url: default_database_url(),
+            invite_url: None,
+        }
+    }
+}
+
+impl Config {
+    /// Loads `slime.toml` from the current directory, falling back to
+    /// defaults if it doesn't exist, then layers `DISCORD_TOKEN` on top.
+    pub fn load() -> Result<Config, ConfigError> {
+        Self::load_from(Path::new(DEFAULT_CONFIG_PATH))
+    }
+
+    pub fn load_from(path: &Path) -> Result<Config, ConfigError> {
+        let mut config = if path.exists() {
+            let contents = fs::read_to_string(path).map_err(ConfigError::Io)?;
+            toml::from_str(&contents).map_err(ConfigError::Toml)?
+        } else {
+            Config::default()
+        };
+
+        if let Ok(env_token) = std::env::var("DISCORD_TOKEN") {
+            config.token = Some(env_token);
+        }
+
+        Ok(config)
+    }
+
+    /// Translates the intent names from the config file into the bitflags
+    /// serenity expects, skipping (and warning about) anything unrecognised.
+    pub fn gateway_intents(&self) -> GatewayIntents {
+        self.intents
+            .iter()
+            .fold(GatewayIntents::empty(), |acc, name| match name.as_str() {
+                "GUILD_MESSAGES" => acc | GatewayIntents::GUILD_MESSAGES,
+                "DIRECT_MESSAGES" => acc | GatewayIntents::DIRECT_MESSAGES,
+                "MESSAGE_CONTENT" => acc | GatewayIntents::MESSAGE_CONTENT,
+                "GUILDS" => acc | GatewayIntents::GUILDS,
+                "GUILD_MEMBERS" => acc | GatewayIntents::GUILD_MEMBERS,
+                other => {
+                    tracing::warn!("Unknown gateway intent in config, ignoring: {other}");
+                    acc
+                }
+            })
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "couldn't read slime.toml: {err}"),
+            ConfigError::Toml(err) => write!(f, "couldn't parse slime.toml: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
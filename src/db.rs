@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter};
+
+use serenity::prelude::TypeMapKey;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+
+/// Typed key for the SQLite pool stored in `client.data`, so commands and
+/// the `message` handler can read and write persistent records.
+pub struct DatabaseContainer;
+
+impl TypeMapKey for DatabaseContainer {
+    type Value = SqlitePool;
+}
+
+/// Opens the SQLite pool at `database_url` and runs the embedded migrations
+/// against it, so the schema is always up to date on startup.
+pub async fn init(database_url: &str) -> Result<SqlitePool, DatabaseError> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(5)
+        .connect(database_url)
+        .await
+        .map_err(DatabaseError::Connect)?;
+
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .map_err(DatabaseError::Migrate)?;
+
+    Ok(pool)
+}
+
+/// Persists one extractor match into `parsed_embeds`, so history accumulates
+/// instead of only ever being logged.
+pub async fn insert_parsed_embed(
+    pool: &SqlitePool,
+    source: &str,
+    fields: &[(String, String)],
+) -> Result<(), DatabaseError> {
+    let fields_json = serde_json::to_string(fields).map_err(DatabaseError::Encode)?;
+
+    sqlx::query("INSERT INTO parsed_embeds (source, fields) VALUES (?, ?)")
+        .bind(source)
+        .bind(fields_json)
+        .execute(pool)
+        .await
+        .map_err(DatabaseError::Query)?;
+
+    Ok(())
+}
+
+/// Looks up the prefix a guild has configured for itself, if any.
+pub async fn guild_prefix(pool: &SqlitePool, guild_id: i64) -> Result<Option<String>, DatabaseError> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT prefix FROM guild_prefixes WHERE guild_id = ?")
+        .bind(guild_id.to_string())
+        .fetch_optional(pool)
+        .await
+        .map_err(DatabaseError::Query)?;
+
+    Ok(row.map(|(prefix,)| prefix))
+}
+
+/// Sets (or replaces) the prefix a guild has configured for itself.
+pub async fn set_guild_prefix(pool: &SqlitePool, guild_id: i64, prefix: &str) -> Result<(), DatabaseError> {
+    sqlx::query(
+        "INSERT INTO guild_prefixes (guild_id, prefix) VALUES (?, ?) \
+         ON CONFLICT(guild_id) DO UPDATE SET prefix = excluded.prefix",
+    )
+    .bind(guild_id.to_string())
+    .bind(prefix)
+    .execute(pool)
+    .await
+    .map_err(DatabaseError::Query)?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum DatabaseError {
+    Connect(sqlx::Error),
+    Migrate(sqlx::migrate::MigrateError),
+    Query(sqlx::Error),
+    Encode(serde_json::Error),
+}
+
+impl Display for DatabaseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatabaseError::Connect(err) => write!(f, "couldn't connect to the database: {err}"),
+            DatabaseError::Migrate(err) => write!(f, "couldn't run database migrations: {err}"),
+            DatabaseError::Query(err) => write!(f, "database query failed: {err}"),
+            DatabaseError::Encode(err) => write!(f, "couldn't encode parsed embed fields: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {}
@@ -0,0 +1,33 @@
+use serenity::model::channel::Embed;
+
+use super::{EmbedExtractor, ParsedData};
+
+/// Matches embeds authored by `author_name` and parses `key: value` lines
+/// out of the embed description. This is the common shape for bots that
+/// post plain "field: value" notifications instead of using real embed
+/// fields, and serves as the template for more specific extractors.
+pub struct KeyValueDescriptionExtractor {
+    pub source: &'static str,
+    pub author_name: &'static str,
+}
+
+impl EmbedExtractor for KeyValueDescriptionExtractor {
+    fn try_parse(&self, embed: &Embed) -> Option<ParsedData> {
+        let author = embed.author.as_ref()?;
+        if author.name != self.author_name {
+            return None;
+        }
+
+        let description = embed.description.as_ref()?;
+        let fields = description
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        Some(ParsedData {
+            source: self.source,
+            fields,
+        })
+    }
+}
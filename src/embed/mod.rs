@@ -0,0 +1,42 @@
+mod key_value;
+
+pub use key_value::KeyValueDescriptionExtractor;
+
+use serenity::model::channel::Embed;
+
+/// Structured data pulled out of a matched embed by an [`EmbedExtractor`].
+#[derive(Debug, Clone)]
+pub struct ParsedData {
+    /// A short label identifying which extractor produced this, so a
+    /// downstream consumer can tell sources apart without re-matching.
+    pub source: &'static str,
+    pub fields: Vec<(String, String)>,
+}
+
+/// Something that knows how to recognise and pull structured fields out of
+/// one particular kind of embed (e.g. a specific bot's notification embeds).
+/// Registering a new extractor is the general mechanism for reacting to
+/// richly-formatted messages from other bots.
+pub trait EmbedExtractor: Send + Sync {
+    /// Returns `Some` if this extractor recognises `embed`, with whatever
+    /// fields it could pull out of it.
+    fn try_parse(&self, embed: &Embed) -> Option<ParsedData>;
+}
+
+/// Runs every registered extractor over `embeds`, in order, keeping the
+/// first match (if any) found for each embed.
+pub fn parse_all(extractors: &[Box<dyn EmbedExtractor>], embeds: &[Embed]) -> Vec<ParsedData> {
+    embeds
+        .iter()
+        .filter_map(|embed| extractors.iter().find_map(|extractor| extractor.try_parse(embed)))
+        .collect()
+}
+
+/// Builds the default set of registered extractors. Add new extractors here
+/// as they're written, rather than special-casing embeds in the handler.
+pub fn default_extractors() -> Vec<Box<dyn EmbedExtractor>> {
+    vec![Box::new(KeyValueDescriptionExtractor {
+        source: "example",
+        author_name: "Example Bot",
+    })]
+}
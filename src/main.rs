@@ -1,34 +1,76 @@
+use std::collections::HashSet;
 use std::env;
 use std::env::VarError;
 use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
 
 use serenity::async_trait;
+use serenity::client::bridge::gateway::ShardManager;
+use serenity::framework::standard::StandardFramework;
+use serenity::http::Http;
 use serenity::model::channel::Message;
+use serenity::model::event::MessageUpdateEvent;
 use serenity::model::gateway::Ready;
 use serenity::prelude::*;
+use tokio::sync::Mutex;
 use tracing::{debug, error, Level, trace};
+use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 use serenity::Error as SerenityError;
 
-struct Handler;
+mod commands;
+mod config;
+mod db;
+mod embed;
+
+use commands::GENERAL_GROUP;
+use config::{Config, ConfigError};
+use db::DatabaseError;
+use embed::EmbedExtractor;
+
+struct ShardManagerContainer;
+
+impl TypeMapKey for ShardManagerContainer {
+    type Value = Arc<Mutex<ShardManager>>;
+}
+
+struct Handler {
+    embed_extractors: Vec<Box<dyn EmbedExtractor>>,
+}
+
+impl Handler {
+    fn new() -> Self {
+        Handler {
+            embed_extractors: embed::default_extractors(),
+        }
+    }
+}
 
 #[async_trait]
 impl EventHandler for Handler {
-    // Set a handler for the `message` event - so that whenever a new message
-    // is received - the closure (or function) passed will be called.
-    //
-    // Event handlers are dispatched through a threadpool, and so multiple
-    // events can be dispatched simultaneously.
+    // Look at every embed on an incoming message, running it through the
+    // registered extractors so other bots' richly-formatted messages can be
+    // turned into structured data and persisted instead of being ignored.
     async fn message(&self, ctx: Context, msg: Message) {
-        if msg.content == "!ping" {
-            // Sending a message can fail, due to a network error, an
-            // authentication error, or lack of permissions to post in the
-            // channel, so log to stdout when some error happens, with a
-            // description of it.
-            if let Err(why) = msg.channel_id.say(&ctx.http, "Pong!").await {
-                println!("Error sending message: {:?}", why);
-            }
-        }
+        store_parsed_embeds(&ctx, embed::parse_all(&self.embed_extractors, &msg.embeds)).await;
+    }
+
+    // Embeds are sometimes attached to a message after it's first sent (e.g.
+    // link unfurling), so re-run extraction whenever a message is edited.
+    async fn message_update(
+        &self,
+        ctx: Context,
+        _old_if_available: Option<Message>,
+        _new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        let Some(embeds) = &event.embeds else {
+            return;
+        };
+
+        store_parsed_embeds(&ctx, embed::parse_all(&self.embed_extractors, embeds)).await;
     }
 
     // Set a handler to be called on the `ready` event. This is called when a
@@ -45,6 +87,8 @@ impl EventHandler for Handler {
 enum SlimeError {
     Var(VarError),
     Serenity(SerenityError),
+    Config(ConfigError),
+    Database(DatabaseError),
 }
 
 impl Debug for SlimeError {
@@ -52,6 +96,8 @@ impl Debug for SlimeError {
         let err_debug_msg = match self {
             SlimeError::Var(err) => { format!("Slime says: \"Variable error: {err:?}\"")}
             SlimeError::Serenity(err) => { format!("Slime says: \"Serenity error: {err:?}\"")}
+            SlimeError::Config(err) => { format!("Slime says: \"Config error: {err:?}\"")}
+            SlimeError::Database(err) => { format!("Slime says: \"Database error: {err:?}\"")}
         };
         write!(f, "{err_debug_msg}")
     }
@@ -62,44 +108,129 @@ impl Display for SlimeError {
         let err_display_msg = match self {
             SlimeError::Var(err) => { format!("SlimeError::Var({err})")}
             SlimeError::Serenity(err) => { format!("SlimeError::SerenityError({err})")}
+            SlimeError::Config(err) => { format!("SlimeError::Config({err})")}
+            SlimeError::Database(err) => { format!("SlimeError::Database({err})")}
         };write!(f, "{err_display_msg}")
     }
 }
 
 impl std::error::Error for SlimeError {}
 
+/// Looks up the per-guild prefix set via the `!prefix` command, if any, so
+/// the framework actually responds to it instead of only ever dispatching
+/// on the static `config.prefix`. Returning `None` falls back to that static
+/// prefix, configured separately via `Configuration::prefix`.
+async fn dynamic_prefix(ctx: &Context, msg: &Message) -> Option<String> {
+    let guild_id = msg.guild_id?;
+    let data = ctx.data.read().await;
+    let pool = data.get::<db::DatabaseContainer>()?;
+
+    match db::guild_prefix(pool, guild_id.0 as i64).await {
+        Ok(prefix) => prefix,
+        Err(l_error) => {
+            error!("Couldn't look up guild prefix: {l_error:?}");
+            None
+        }
+    }
+}
+
+/// Persists every parsed embed into the `parsed_embeds` table so they
+/// accumulate as queryable history rather than only being logged.
+async fn store_parsed_embeds(ctx: &Context, parsed: Vec<embed::ParsedData>) {
+    if parsed.is_empty() {
+        return;
+    }
+
+    let data = ctx.data.read().await;
+    let Some(pool) = data.get::<db::DatabaseContainer>() else {
+        error!("No database pool found, dropping {} parsed embed(s).", parsed.len());
+        return;
+    };
+
+    for entry in parsed {
+        if let Err(l_error) = db::insert_parsed_embed(pool, entry.source, &entry.fields).await {
+            error!("Couldn't persist parsed embed from {}: {l_error:?}", entry.source);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), SlimeError> {
-    let level = if cfg!(debug_assertions) {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(l_error) => {
+            eprintln!("Couldn't load config: {l_error:?}");
+            return Err(SlimeError::Config(l_error));
+        }
+    };
+
+    let default_level = if cfg!(debug_assertions) {
         Level::DEBUG
     } else {
         Level::INFO
     };
+    let level = config.log_level.parse().unwrap_or(default_level);
 
-    init_tracing(level);
+    // Held for the lifetime of `main` so the non-blocking file writer keeps
+    // flushing; dropping it early would silently stop log file writes.
+    let _tracing_guard = init_tracing(level, &config.log_dir, &config.log_file_prefix);
 
-    trace!("Beginning everything. Now retrieving the DISCORD_TOKEN...");
+    trace!("Beginning everything. Config loaded from slime.toml.");
 
-    // Configure the client with your Discord bot token in the environment.
-    let token = match env::var("DISCORD_TOKEN") {
-        Ok(token) => {
-            debug!("Here's your token: {token}");
+    // The token may come from slime.toml or from DISCORD_TOKEN (which always
+    // wins if set); if neither supplies one, surface the env var lookup
+    // failure since that's the more actionable error message.
+    let token = match config.token.clone() {
+        Some(token) => {
+            debug!("Token loaded from slime.toml or DISCORD_TOKEN.");
             token
         }
-        Err(l_error) => {
+        None => {
+            let l_error = env::var("DISCORD_TOKEN").unwrap_err();
             error!("Couldn't retrieve the token: {l_error:?}");
             return Err(SlimeError::Var(l_error));
         }
     };
 
-    let intents = GatewayIntents::GUILD_MESSAGES
-        | GatewayIntents::DIRECT_MESSAGES
-        | GatewayIntents::MESSAGE_CONTENT;
+    let intents = config.gateway_intents();
 
     trace!("Intent selected: {intents:?}");
 
+    let db_pool = match db::init(&config.database_url).await {
+        Ok(pool) => pool,
+        Err(l_error) => {
+            error!("Couldn't initialize the database: {l_error:?}");
+            return Err(SlimeError::Database(l_error));
+        }
+    };
+
+    // Fetch the application's owner(s) up front so owner-restricted commands
+    // (e.g. `quit`) can be gated without anyone else being able to invoke them.
+    let http = Http::new(&token);
+    let owners = match http.get_current_application_info().await {
+        Ok(info) => {
+            let mut owners = HashSet::new();
+            if let Some(team) = &info.team {
+                owners.extend(team.members.iter().map(|member| member.user.id));
+            } else {
+                owners.insert(info.owner.id);
+            }
+            owners
+        }
+        Err(l_error) => {
+            error!("Couldn't fetch application info to determine bot owners: {l_error:?}");
+            return Err(SlimeError::Serenity(l_error));
+        }
+    };
+
+    let framework = StandardFramework::new()
+        .configure(|c| c.prefix(&config.prefix).owners(owners))
+        .dynamic_prefix(|ctx, msg| Box::pin(dynamic_prefix(ctx, msg)))
+        .group(&GENERAL_GROUP);
+
     let mut client = match Client::builder(&token, intents)
-        // .event_handler(Handler)
+        .event_handler(Handler::new())
+        .framework(framework)
         .await {
         Ok(client) => { client }
         Err(l_error) => {
@@ -108,6 +239,12 @@ async fn main() -> Result<(), SlimeError> {
         }
     };
 
+    {
+        let mut data = client.data.write().await;
+        data.insert::<ShardManagerContainer>(Arc::clone(&client.shard_manager));
+        data.insert::<db::DatabaseContainer>(db_pool);
+    }
+
     // Finally, start a single shard, and start listening to events.
     //
     // Shards will automatically attempt to reconnect, and will perform
@@ -120,8 +257,26 @@ async fn main() -> Result<(), SlimeError> {
 }
 
 
-fn init_tracing(filter: impl Into<LevelFilter>) {
-    tracing_subscriber::fmt()
-        .with_max_level(filter)
-        .init()
+/// Installs a stdout layer plus a daily-rotating file layer under
+/// `log_dir/log_file_prefix.*`, both filtered to `filter`. Returns the
+/// `WorkerGuard` for the non-blocking file writer; it must be kept alive for
+/// the process lifetime so buffered logs actually get flushed to disk.
+fn init_tracing(filter: impl Into<LevelFilter>, log_dir: &str, file_prefix: &str) -> WorkerGuard {
+    let filter = filter.into();
+
+    let file_appender = tracing_appender::rolling::daily(log_dir, file_prefix);
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+
+    let stdout_layer = tracing_subscriber::fmt::layer();
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_writer(non_blocking)
+        .with_ansi(false);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(stdout_layer)
+        .with(file_layer)
+        .init();
+
+    guard
 }